@@ -1,14 +1,119 @@
-use std::{borrow::Cow, error, fmt};
+use std::{
+    borrow::Cow,
+    error, fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use crossbeam::channel::{unbounded, Sender};
-use log::{LevelFilter, Log, Metadata, Record};
+use crossbeam::channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use log::{Level, LevelFilter, Log, Metadata, Record};
 use once_cell::sync::OnceCell;
 
 static INNER_LOGGER: OnceCell<Box<dyn Log>> = OnceCell::new();
 
+/// A unit of work sent to the worker thread.
+enum Msg {
+    /// Replay a deferred log record against the inner logger.
+    Log(Box<dyn FnOnce() + Send>),
+    /// Flush the inner logger and signal completion through the given sender.
+    ///
+    /// Because the channel is FIFO, every `Log` message enqueued before this
+    /// one has already been processed by the time the signal fires.
+    Flush(Sender<()>),
+    /// Stop the worker loop after draining and flushing whatever is left.
+    Quit,
+}
+
+/// What to do when the channel to the worker is at capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Block the calling thread until the worker makes room.
+    Block,
+    /// Discard the record that was about to be enqueued.
+    DropNewest,
+    /// Discard the oldest queued record to make room for the new one.
+    DropOldest,
+}
+
+/// A parsed `env_logger`-style directive string, e.g.
+/// `"info,my_crate=debug,my_crate::noisy=off"`: a default level plus a set of
+/// per-target overrides matched by longest prefix.
+struct DirectiveFilter {
+    default: LevelFilter,
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl DirectiveFilter {
+    fn parse(spec: &str) -> Self {
+        // Matches `env_logger`/`env_filter`: a spec with no bare catch-all
+        // token (e.g. `"my_crate=debug"`) filters out every other target
+        // entirely rather than admitting them at full verbosity.
+        let mut default = LevelFilter::Off;
+        let mut directives = Vec::new();
+
+        for directive in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Ok(level) = level.parse() {
+                        directives.push((target.to_owned(), level));
+                    }
+                }
+                None => {
+                    if let Ok(level) = directive.parse() {
+                        default = level;
+                    }
+                }
+            }
+        }
+
+        // Longest prefix wins, so check the most specific targets first.
+        directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+
+        DirectiveFilter { default, directives }
+    }
+
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let level = self
+            .directives
+            .iter()
+            .find(|(target, _)| metadata.target().starts_with(target.as_str()))
+            .map_or(self.default, |(_, level)| *level);
+
+        metadata.level() <= level
+    }
+
+    /// The most verbose level named anywhere in this filter (the default or
+    /// any per-target override), i.e. the level below which `log::max_level`
+    /// must never be set without defeating this filter before it ever runs.
+    fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, |acc, level| acc.max(level))
+    }
+}
+
+/// The calling thread's name, falling back to a readable id-based label when
+/// the thread was never given one (e.g. one started with plain `thread::spawn`).
+fn current_thread_label() -> String {
+    let current = thread::current();
+    match current.name() {
+        Some(name) => name.to_owned(),
+        None => format!("thread-{:?}", current.id()),
+    }
+}
+
 struct ThreadedLogger {
     logger: &'static dyn Log,
-    sender: Sender<Box<dyn FnOnce() + Send>>,
+    sender: Sender<Msg>,
+    receiver: Receiver<Msg>,
+    policy: OverflowPolicy,
+    dropped: Arc<AtomicU64>,
+    filter: Option<DirectiveFilter>,
 }
 
 impl Log for ThreadedLogger {
@@ -17,6 +122,12 @@ impl Log for ThreadedLogger {
     }
 
     fn log(&self, record: &Record) {
+        if let Some(filter) = &self.filter {
+            if !filter.enabled(record.metadata()) {
+                return;
+            }
+        }
+
         let level = record.metadata().level();
         let target = record.metadata().target().to_owned();
 
@@ -40,10 +151,17 @@ impl Log for ThreadedLogger {
 
         let line = record.line();
 
+        let ts = SystemTime::now();
+        let thread_name = current_thread_label();
+
         let logger_ = self.logger.clone();
         let log = move || {
             let metadata = Metadata::builder().level(level).target(&target).build();
 
+            let since_epoch = ts.duration_since(UNIX_EPOCH).unwrap_or_default();
+            let ts_value = format!("{}.{:09}", since_epoch.as_secs(), since_epoch.subsec_nanos());
+            let kvs: [(&str, &str); 2] = [("ts", ts_value.as_str()), ("thread", &thread_name)];
+
             logger_.log(
                 &Record::builder()
                     .metadata(metadata)
@@ -51,48 +169,291 @@ impl Log for ThreadedLogger {
                     .module_path(module_path.as_deref())
                     .file(file.as_deref())
                     .line(line)
+                    .key_values(&kvs)
                     .build(),
             );
         };
 
-        self.sender.send(Box::new(log)).ok();
+        let msg = Msg::Log(Box::new(log));
+        match self.policy {
+            OverflowPolicy::Block => {
+                self.sender.send(msg).ok();
+            }
+            OverflowPolicy::DropNewest => {
+                if self.sender.try_send(msg).is_err() {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            OverflowPolicy::DropOldest => {
+                if let Err(TrySendError::Full(msg)) = self.sender.try_send(msg) {
+                    // Evict the oldest *Log* entry only: a `Flush`/`Quit`
+                    // sitting ahead of it must never be discarded, so any
+                    // control message we pop along the way gets requeued.
+                    let mut requeued = Vec::new();
+                    let mut evicted = false;
+                    while !evicted {
+                        match self.receiver.try_recv() {
+                            Ok(Msg::Log(_)) => {
+                                self.dropped.fetch_add(1, Ordering::Relaxed);
+                                evicted = true;
+                            }
+                            Ok(control) => requeued.push(control),
+                            Err(_) => break,
+                        }
+                    }
+                    for control in requeued {
+                        self.sender.send(control).ok();
+                    }
+                    if self.sender.try_send(msg).is_err() {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
     }
 
     fn flush(&self) {
-        self.logger.flush()
+        let (tx, rx) = unbounded();
+        if self.sender.send(Msg::Flush(tx)).is_ok() {
+            rx.recv().ok();
+        }
     }
 }
 
-pub fn try_init(
+/// What a worker backend needs to drain the channel: the sender handed to
+/// later callers, the receiver to poll, and the dropped-message counter to
+/// report on.
+struct WorkerChannels {
+    sender: Sender<Msg>,
+    receiver: Receiver<Msg>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// Register `logger` behind a `ThreadedLogger` and hand back the channel the
+/// worker backend should drain, plus the shared dropped-message counter.
+fn setup(
     logger: impl Log + 'static,
     max_level: LevelFilter,
-) -> Result<(), ThreadedLoggerError> {
-    let (sender, receiver) = unbounded();
+    capacity: usize,
+    policy: OverflowPolicy,
+    filter: Option<&str>,
+) -> Result<WorkerChannels, ThreadedLoggerError> {
+    let (sender, receiver) = bounded(capacity);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let filter = filter.map(DirectiveFilter::parse);
+
+    // `log!` macros gate on `log::max_level()` before a record ever reaches
+    // `ThreadedLogger::log`, so a directive asking for a more verbose level
+    // than the caller's `max_level` would otherwise be silently defeated.
+    // Widen the global level to whatever the filter actually allows through.
+    let effective_max_level = filter
+        .as_ref()
+        .map_or(max_level, |f| f.max_level().max(max_level));
 
     INNER_LOGGER
         .set(Box::new(logger))
         .map_err(|_| ThreadedLoggerError(()))?;
     let threaded_logger = ThreadedLogger {
         logger: unsafe { INNER_LOGGER.get_unchecked() },
-        sender,
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        policy,
+        dropped: dropped.clone(),
+        filter,
     };
 
-    let r = log::set_boxed_logger(Box::new(threaded_logger)).map_err(|_| ThreadedLoggerError(()));
-    if r.is_ok() {
-        log::set_max_level(max_level);
+    log::set_boxed_logger(Box::new(threaded_logger)).map_err(|_| ThreadedLoggerError(()))?;
+    log::set_max_level(effective_max_level);
+
+    Ok(WorkerChannels {
+        sender,
+        receiver,
+        dropped,
+    })
+}
+
+/// Emit one synthetic warning record through `logger` summarizing how many
+/// messages have been discarded since the last report, if any.
+fn report_dropped(logger: &dyn Log, dropped: &AtomicU64) {
+    let n = dropped.swap(0, Ordering::Relaxed);
+    if n > 0 {
+        logger.log(
+            &Record::builder()
+                .level(Level::Warn)
+                .target("threaded_logger")
+                .args(format_args!(
+                    "threaded_logger dropped {} message(s) due to overflow",
+                    n
+                ))
+                .build(),
+        );
     }
+}
 
-    tokio::task::spawn_blocking(move || loop {
-        if let Ok(log) = receiver.recv() {
-            log();
+/// Pop `Msg`s off `receiver` and replay them against `logger` until a `Quit`
+/// (or a closed channel) is seen, draining whatever is left before returning.
+fn worker_loop(receiver: Receiver<Msg>, logger: &'static dyn Log, dropped: Arc<AtomicU64>) {
+    loop {
+        match receiver.recv() {
+            Ok(Msg::Log(log)) => log(),
+            Ok(Msg::Flush(tx)) => {
+                logger.flush();
+                report_dropped(logger, &dropped);
+                tx.send(()).ok();
+            }
+            Ok(Msg::Quit) | Err(_) => {
+                let mut flushes = Vec::new();
+                loop {
+                    match receiver.try_recv() {
+                        Ok(Msg::Log(log)) => log(),
+                        Ok(Msg::Flush(tx)) => flushes.push(tx),
+                        Ok(Msg::Quit) => {}
+                        Err(_) => break,
+                    }
+                }
+                report_dropped(logger, &dropped);
+                logger.flush();
+                for tx in flushes {
+                    tx.send(()).ok();
+                }
+                break;
+            }
         }
-    });
+    }
+}
+
+/// Start the worker on a dedicated OS thread, requiring no async runtime.
+///
+/// `capacity` bounds the channel to the worker; `policy` decides what happens
+/// when a record is logged while the channel is full; `filter` is an optional
+/// `env_logger`-style directive string (e.g. `"info,my_crate=debug"`) applied
+/// before a record is ever enqueued. The global `log::max_level` ends up as
+/// the more verbose of `max_level` and whatever `filter` allows, so a
+/// directive is never capped by `max_level`; records that `filter` does not
+/// admit are still dropped before they are enqueued.
+pub fn try_init_thread(
+    logger: impl Log + 'static,
+    max_level: LevelFilter,
+    capacity: usize,
+    policy: OverflowPolicy,
+    filter: Option<&str>,
+) -> Result<ThreadedLoggerHandle, ThreadedLoggerError> {
+    let WorkerChannels {
+        sender,
+        receiver,
+        dropped,
+    } = setup(logger, max_level, capacity, policy, filter)?;
+
+    let logger_ = unsafe { INNER_LOGGER.get_unchecked() };
+    let worker_dropped = dropped.clone();
+    let worker = thread::Builder::new()
+        .name("threaded-logger".to_owned())
+        .spawn(move || worker_loop(receiver, logger_, worker_dropped))
+        .map_err(|_| ThreadedLoggerError(()))?;
 
-    r
+    Ok(ThreadedLoggerHandle {
+        sender,
+        worker,
+        dropped,
+    })
+}
+
+pub fn init_thread(
+    logger: impl Log + 'static,
+    max_level: LevelFilter,
+    capacity: usize,
+    policy: OverflowPolicy,
+    filter: Option<&str>,
+) -> ThreadedLoggerHandle {
+    try_init_thread(logger, max_level, capacity, policy, filter).unwrap()
+}
+
+/// A handle to the background worker thread, returned by [`try_init_thread`] and
+/// [`init_thread`].
+///
+/// Dropping the handle leaves the worker running detached; call [`shutdown`](Self::shutdown)
+/// to flush pending logs and wait for the worker to stop.
+pub struct ThreadedLoggerHandle {
+    sender: Sender<Msg>,
+    worker: thread::JoinHandle<()>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl ThreadedLoggerHandle {
+    /// How many records have been discarded by the overflow policy so far.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Ask the worker to drain any queued logs, flush the inner logger, and stop.
+    pub fn shutdown(self) {
+        self.sender.send(Msg::Quit).ok();
+        self.worker.join().ok();
+    }
 }
 
-pub fn init(logger: impl Log + 'static, max_level: LevelFilter) {
-    try_init(logger, max_level).unwrap();
+/// Start the worker on `tokio::task::spawn_blocking`, requiring a tokio runtime
+/// to already be running. Prefer [`try_init_thread`] unless you specifically
+/// want the worker to live on tokio's blocking thread pool.
+#[cfg(feature = "tokio")]
+pub fn try_init(
+    logger: impl Log + 'static,
+    max_level: LevelFilter,
+    capacity: usize,
+    policy: OverflowPolicy,
+    filter: Option<&str>,
+) -> Result<TokioThreadedLoggerHandle, ThreadedLoggerError> {
+    let WorkerChannels {
+        sender,
+        receiver,
+        dropped,
+    } = setup(logger, max_level, capacity, policy, filter)?;
+
+    let logger_ = unsafe { INNER_LOGGER.get_unchecked() };
+    let worker_dropped = dropped.clone();
+    let worker = tokio::task::spawn_blocking(move || worker_loop(receiver, logger_, worker_dropped));
+
+    Ok(TokioThreadedLoggerHandle {
+        sender,
+        worker,
+        dropped,
+    })
+}
+
+#[cfg(feature = "tokio")]
+pub fn init(
+    logger: impl Log + 'static,
+    max_level: LevelFilter,
+    capacity: usize,
+    policy: OverflowPolicy,
+    filter: Option<&str>,
+) -> TokioThreadedLoggerHandle {
+    try_init(logger, max_level, capacity, policy, filter).unwrap()
+}
+
+/// A handle to the background worker task, returned by [`try_init`] and [`init`].
+///
+/// Dropping the handle leaves the worker running detached; call [`shutdown`](Self::shutdown)
+/// to flush pending logs and wait for the worker to stop.
+#[cfg(feature = "tokio")]
+pub struct TokioThreadedLoggerHandle {
+    sender: Sender<Msg>,
+    worker: tokio::task::JoinHandle<()>,
+    dropped: Arc<AtomicU64>,
+}
+
+#[cfg(feature = "tokio")]
+impl TokioThreadedLoggerHandle {
+    /// How many records have been discarded by the overflow policy so far.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Ask the worker to drain any queued logs, flush the inner logger, and stop.
+    pub async fn shutdown(self) {
+        self.sender.send(Msg::Quit).ok();
+        self.worker.await.ok();
+    }
 }
 
 #[derive(Debug)]
@@ -109,19 +470,458 @@ impl error::Error for ThreadedLoggerError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn flush_waits_for_previously_queued_records() {
+        struct SlowLogger {
+            records: Mutex<Vec<String>>,
+        }
+
+        impl Log for SlowLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                thread::sleep(Duration::from_millis(20));
+                self.records.lock().unwrap().push(record.args().to_string());
+            }
 
-    #[tokio::test]
-    async fn threaded_env_logger() {
-        let logger = env_logger::builder().build();
-        let filter = logger.filter();
+            fn flush(&self) {}
+        }
+
+        let inner: &'static SlowLogger = Box::leak(Box::new(SlowLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(8);
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            policy: OverflowPolicy::Block,
+            dropped: Arc::new(AtomicU64::new(0)),
+            filter: None,
+        };
 
-        init(logger, filter);
+        let worker = thread::spawn(move || worker_loop(receiver, inner, Arc::new(AtomicU64::new(0))));
 
-        let now = std::time::Instant::now();
-        for i in 0..100000 {
-            log::info!("{}", i);
+        for i in 0..5 {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .target("t")
+                    .args(format_args!("{}", i))
+                    .build(),
+            );
         }
-        let t = now.elapsed().as_micros();
-        println!("time elapsed: {}Âµs", t);
+
+        logger.flush();
+        assert_eq!(
+            inner.records.lock().unwrap().len(),
+            5,
+            "flush() must not return before every previously queued record is processed"
+        );
+
+        logger.sender.send(Msg::Quit).ok();
+        worker.join().unwrap();
+    }
+
+    /// An inner `Log` that just appends `target:args` to a shared `Vec`, for
+    /// asserting on what actually made it through.
+    struct RecordingLogger {
+        records: Mutex<Vec<String>>,
+    }
+
+    impl Log for RecordingLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.records
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", record.target(), record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn log_record(logger: &ThreadedLogger, i: u32) {
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .target("t")
+                .args(format_args!("{}", i))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn drop_newest_counts_every_rejected_record() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(1);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender,
+            receiver,
+            policy: OverflowPolicy::DropNewest,
+            dropped: dropped.clone(),
+            filter: None,
+        };
+
+        // The channel has capacity 1 and nothing is draining it, so the first
+        // record fills it and every subsequent one is rejected outright.
+        for i in 0..4 {
+            log_record(&logger, i);
+        }
+
+        assert_eq!(dropped.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn drop_oldest_drops_the_new_record_when_only_a_flush_can_be_evicted() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(1);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver,
+            policy: OverflowPolicy::DropOldest,
+            dropped: dropped.clone(),
+            filter: None,
+        };
+
+        // Fill the sole slot with a `Flush` that nothing will ever answer, then
+        // log a record: with nothing evictable (the lone queued item is a
+        // control message), the incoming record is the one that gets dropped,
+        // and the `Flush` is left untouched rather than discarded.
+        let (tx, _rx) = unbounded();
+        sender.send(Msg::Flush(tx)).unwrap();
+        log_record(&logger, 0);
+
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+        assert!(matches!(logger.receiver.try_recv(), Ok(Msg::Flush(_))));
+        assert!(logger.receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn drop_oldest_evicts_the_oldest_log_behind_a_flush() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(2);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver,
+            policy: OverflowPolicy::DropOldest,
+            dropped: dropped.clone(),
+            filter: None,
+        };
+
+        let (tx, _rx) = unbounded();
+        sender.send(Msg::Flush(tx)).unwrap();
+        log_record(&logger, 0);
+        // Channel is now full: [Flush, Log(0)]. Logging once more must evict
+        // Log(0), not the Flush ahead of it.
+        log_record(&logger, 1);
+
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+        assert!(matches!(logger.receiver.try_recv(), Ok(Msg::Flush(_))));
+        assert!(matches!(logger.receiver.try_recv(), Ok(Msg::Log(_))));
+    }
+
+    #[test]
+    fn report_dropped_emits_once_and_resets() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+        let dropped = AtomicU64::new(3);
+
+        report_dropped(inner, &dropped);
+        assert_eq!(inner.records.lock().unwrap().len(), 1);
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+
+        report_dropped(inner, &dropped);
+        assert_eq!(
+            inner.records.lock().unwrap().len(),
+            1,
+            "nothing was dropped since the last report, so nothing new should be logged"
+        );
+    }
+
+    #[test]
+    fn shutdown_drains_queued_logs_before_joining() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(8);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            policy: OverflowPolicy::Block,
+            dropped: dropped.clone(),
+            filter: None,
+        };
+
+        let worker_dropped = dropped.clone();
+        let worker = thread::Builder::new()
+            .name("threaded-logger".to_owned())
+            .spawn(move || worker_loop(receiver, inner, worker_dropped))
+            .unwrap();
+
+        for i in 0..10 {
+            log_record(&logger, i);
+        }
+
+        let handle = ThreadedLoggerHandle {
+            sender,
+            worker,
+            dropped,
+        };
+
+        handle.shutdown();
+
+        assert_eq!(
+            inner.records.lock().unwrap().len(),
+            10,
+            "shutdown() must drain every queued record before the worker stops"
+        );
+    }
+
+    #[test]
+    fn worker_thread_backend_runs_as_a_plain_os_thread_named_threaded_logger() {
+        let inner: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        let (sender, receiver) = bounded(4);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let logger = ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            policy: OverflowPolicy::Block,
+            dropped: dropped.clone(),
+            filter: None,
+        };
+
+        // Mirrors exactly what `try_init_thread` spawns: a plain named OS
+        // thread, no async runtime involved.
+        let worker_dropped = dropped.clone();
+        let worker = thread::Builder::new()
+            .name("threaded-logger".to_owned())
+            .spawn(move || worker_loop(receiver, inner, worker_dropped))
+            .unwrap();
+
+        assert_eq!(worker.thread().name(), Some("threaded-logger"));
+
+        log_record(&logger, 0);
+        logger.sender.send(Msg::Quit).ok();
+        worker.join().unwrap();
+
+        assert_eq!(
+            inner.records.lock().unwrap().len(),
+            1,
+            "the OS-thread backend must process records without any async runtime present"
+        );
+    }
+
+    #[test]
+    fn current_thread_label_falls_back_to_a_readable_id_when_unnamed() {
+        let label = thread::Builder::new()
+            .spawn(current_thread_label)
+            .unwrap()
+            .join()
+            .unwrap();
+
+        assert!(
+            label.starts_with("thread-"),
+            "unnamed thread label was {label:?}"
+        );
+    }
+
+    #[test]
+    fn log_key_values_capture_the_call_sites_timestamp_and_thread_name() {
+        use log::kv::Key;
+
+        struct KvCapturingLogger {
+            has_ts: Mutex<bool>,
+            thread_name: Mutex<String>,
+        }
+
+        impl Log for KvCapturingLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                let kvs = record.key_values();
+                *self.has_ts.lock().unwrap() = kvs.get(Key::from("ts")).is_some();
+                if let Some(value) = kvs.get(Key::from("thread")) {
+                    *self.thread_name.lock().unwrap() = value.to_string();
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        let inner: &'static KvCapturingLogger = Box::leak(Box::new(KvCapturingLogger {
+            has_ts: Mutex::new(false),
+            thread_name: Mutex::new(String::new()),
+        }));
+
+        let (sender, receiver) = bounded(8);
+        let logger = Arc::new(ThreadedLogger {
+            logger: inner,
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            policy: OverflowPolicy::Block,
+            dropped: Arc::new(AtomicU64::new(0)),
+            filter: None,
+        });
+
+        let worker = thread::spawn(move || worker_loop(receiver, inner, Arc::new(AtomicU64::new(0))));
+
+        // Log from a thread with a known name so the captured "thread" value
+        // can be checked against it: the key-value must reflect the caller
+        // that logged the record, not the worker that replays it.
+        let caller_logger = logger.clone();
+        let caller = thread::Builder::new()
+            .name("kv-test-caller".to_owned())
+            .spawn(move || {
+                caller_logger.log(
+                    &Record::builder()
+                        .level(Level::Info)
+                        .target("t")
+                        .args(format_args!("hi"))
+                        .build(),
+                );
+                caller_logger.flush();
+            })
+            .unwrap();
+        caller.join().unwrap();
+
+        assert!(
+            *inner.has_ts.lock().unwrap(),
+            "a replayed record must carry a \"ts\" key-value"
+        );
+        assert_eq!(*inner.thread_name.lock().unwrap(), "kv-test-caller");
+
+        logger.sender.send(Msg::Quit).ok();
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn directive_filter_matches_longest_target_prefix() {
+        let filter = DirectiveFilter::parse("info,my_crate=debug,my_crate::noisy=off");
+
+        let admitted = Metadata::builder()
+            .level(Level::Debug)
+            .target("my_crate::handler")
+            .build();
+        assert!(filter.enabled(&admitted));
+
+        let suppressed_by_default = Metadata::builder()
+            .level(Level::Debug)
+            .target("other_crate")
+            .build();
+        assert!(!filter.enabled(&suppressed_by_default));
+
+        let suppressed_by_override = Metadata::builder()
+            .level(Level::Warn)
+            .target("my_crate::noisy")
+            .build();
+        assert!(!filter.enabled(&suppressed_by_override));
+    }
+
+    #[test]
+    fn directive_filter_without_bare_default_suppresses_unmatched_targets() {
+        let filter = DirectiveFilter::parse("my_crate=debug");
+
+        let matched = Metadata::builder()
+            .level(Level::Debug)
+            .target("my_crate::handler")
+            .build();
+        assert!(filter.enabled(&matched));
+
+        let unmatched = Metadata::builder()
+            .level(Level::Error)
+            .target("other_crate")
+            .build();
+        assert!(
+            !filter.enabled(&unmatched),
+            "a spec with no bare catch-all must suppress targets it doesn't name, not admit them at Trace"
+        );
+    }
+
+    #[test]
+    fn directive_filter_max_level_spans_default_and_overrides() {
+        let filter = DirectiveFilter::parse("warn,my_crate=debug,my_crate::noisy=off");
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn directive_can_widen_logging_past_the_caller_supplied_max_level() {
+        let captured: &'static RecordingLogger = Box::leak(Box::new(RecordingLogger {
+            records: Mutex::new(Vec::new()),
+        }));
+
+        struct ForwardingLogger {
+            inner: &'static RecordingLogger,
+        }
+
+        impl Log for ForwardingLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                self.inner.log(record);
+            }
+
+            fn flush(&self) {}
+        }
+
+        // The caller asks for `Info`, but the directive wants `debug!` on
+        // `loud` to come through regardless; `log::max_level` must end up
+        // wide enough for that record to even reach `ThreadedLogger::log`.
+        let handle = init_thread(
+            ForwardingLogger { inner: captured },
+            LevelFilter::Info,
+            16,
+            OverflowPolicy::Block,
+            Some("info,loud=debug"),
+        );
+
+        log::debug!(target: "loud", "should be delivered");
+        log::debug!(target: "quiet", "should be suppressed by the default level");
+        log::info!(target: "quiet", "should be delivered");
+
+        log::logger().flush();
+        assert_eq!(handle.dropped_count(), 0);
+        handle.shutdown();
+
+        let records = captured.records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().any(|r| r.contains("should be delivered")));
+        assert!(!records.iter().any(|r| r.contains("should be suppressed")));
     }
 }